@@ -18,34 +18,284 @@
 //! using the cli to manufacture transactions and distribute them
 //! to accounts.
 
-use std::time::{SystemTime, UNIX_EPOCH};
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 
+use bip39::{Mnemonic, Language};
+use futures::{Future, Stream};
+use lazy_static::lazy_static;
+use log::info;
+use parking_lot::RwLock;
 use rand::{Rng, SeedableRng};
 use rand::rngs::StdRng;
+use substrate_bip39::mini_secret_from_entropy;
 
 use balances::Call as BalancesCall;
+use client::BlockchainEvents;
+use client::blockchain::HeaderBackend;
 use parity_codec::Decode;
 use keyring::sr25519::Keyring;
 use node_primitives::Hash;
-use node_runtime::{Call, CheckedExtrinsic, UncheckedExtrinsic};
-use primitives::sr25519;
-use primitives::crypto::Pair;
+use node_runtime::{Call, CheckedExtrinsic, Runtime, UncheckedExtrinsic};
+use primitives::{ecdsa, ed25519, sr25519, MultiSignature, MultiSigner};
+use primitives::crypto::{DeriveJunction, IdentifyAccount, Pair};
+use primitives::storage::StorageKey;
 use parity_codec::Encode;
-use sr_primitives::generic::Era;
-use sr_primitives::traits::{As, Block as BlockT};
-use substrate_service::ServiceFactory;
+use sr_primitives::generic::{BlockId, Era};
+use sr_primitives::traits::{As, Block as BlockT, Header as HeaderT};
+use substrate_service::{ComponentClient, FullComponents, ServiceFactory};
 use transaction_factory::RuntimeAdapter;
 use crate::service;
 
+/// The longevity, in blocks, given to every mortal extrinsic the factory
+/// manufactures.
+const MORTAL_PERIOD: u64 = 256;
+
+type FullClient = ComponentClient<FullComponents<service::Factory>>;
+
+lazy_static! {
+	// The node client the adapter's associated functions read live chain
+	// state from. `RuntimeAdapter`'s functions carry no `self`, so this is
+	// populated once via `RuntimeAdapterImpl::init` before a factory run
+	// starts, rather than threaded through every call.
+	static ref CLIENT: RwLock<Option<Arc<FullClient>>> = RwLock::new(None);
+
+	// Which `CryptoScheme` `gen_random_account_secret` and friends dispatch
+	// to. Set once via `RuntimeAdapterImpl::set_scheme`; defaults to the
+	// `sr25519` behaviour the factory always had.
+	static ref SCHEME: RwLock<CryptoScheme> = RwLock::new(CryptoScheme::Sr25519);
+
+	// The master account's next nonce, for `CallKind::Faucet`. Queried from
+	// storage only to seed the counter on first use, then bumped in-process
+	// on every subsequent call -- a fresh `extract_index` per call would
+	// return the same, not-yet-included nonce for every Faucet extrinsic
+	// manufactured against the same `prior_block_hash`, so all but the first
+	// would collide in the pool.
+	static ref MASTER_INDEX: RwLock<Option<node_primitives::Index>> = RwLock::new(None);
+}
+
+/// The signature scheme a factory account's key uses. Making this a
+/// parameter of the adapter, instead of hardwiring `sr25519`, lets a run
+/// manufacture mixed-key populations and exercise each scheme's
+/// signature-verification path under load.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CryptoScheme {
+	Sr25519,
+	Ed25519,
+	Ecdsa,
+}
+
+/// A `Secret` tagged with the scheme it was generated under -- the private
+/// counterpart to `primitives::MultiSigner`/`MultiSignature`'s tagged union
+/// of public artifacts.
+#[derive(Clone)]
+pub enum AnySecret {
+	Sr25519(sr25519::Pair),
+	Ed25519(ed25519::Pair),
+	Ecdsa(ecdsa::Pair),
+}
+
+impl AnySecret {
+	fn from_seed(scheme: CryptoScheme, seed: [u8; 32]) -> Self {
+		match scheme {
+			CryptoScheme::Sr25519 => AnySecret::Sr25519(sr25519::Pair::from_seed(seed)),
+			CryptoScheme::Ed25519 => AnySecret::Ed25519(ed25519::Pair::from_seed(seed)),
+			CryptoScheme::Ecdsa => AnySecret::Ecdsa(ecdsa::Pair::from_seed(seed)),
+		}
+	}
+
+	fn signer(&self) -> MultiSigner {
+		match self {
+			AnySecret::Sr25519(pair) => MultiSigner::Sr25519(pair.public()),
+			AnySecret::Ed25519(pair) => MultiSigner::Ed25519(pair.public()),
+			AnySecret::Ecdsa(pair) => MultiSigner::Ecdsa(pair.public()),
+		}
+	}
+
+	/// The `AccountId` a keystore would derive for this key, tagged the same
+	/// way `MultiSigner::into_account` tags it for each scheme.
+	fn account_id(&self) -> node_primitives::AccountId {
+		self.signer().into_account()
+	}
+
+	fn sign(&self, message: &[u8]) -> MultiSignature {
+		match self {
+			AnySecret::Sr25519(pair) => MultiSignature::Sr25519(pair.sign(message)),
+			AnySecret::Ed25519(pair) => MultiSignature::Ed25519(pair.sign(message)),
+			AnySecret::Ecdsa(pair) => MultiSignature::Ecdsa(pair.sign(message)),
+		}
+	}
+}
+
+/// The kinds of calls the factory can manufacture. Paired with a weight in a
+/// tx-mix string (e.g. `transfer=70,remark=30`, parsed by `parse_tx_mix`),
+/// these let a run emit a realistic, heterogeneous blend of load instead of
+/// pure transfers. This module does not add a command-line option for the
+/// tx-mix string.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CallKind {
+	/// A `Balances::transfer` between two factory accounts.
+	Transfer,
+	/// A `Balances::transfer` from the master account topping up `sender`,
+	/// mimicking a faucet.
+	Faucet,
+	/// A `System::remark` no-op, for measuring pure throughput without
+	/// touching any account's balance.
+	Remark,
+}
+
+/// One entry of a tx-mix: a `CallKind` and the relative weight it should
+/// be given among the other entries.
+#[derive(Clone, Copy, Debug)]
+pub struct CallKindWeight {
+	pub kind: CallKind,
+	pub weight: u32,
+}
+
+/// Parses a tx-mix string such as `transfer=70,remark=30` into the weights
+/// `pick_call_kind` selects from.
+pub fn parse_tx_mix(spec: &str) -> Result<Vec<CallKindWeight>, String> {
+	spec.split(',')
+		.map(|entry| {
+			let mut parts = entry.splitn(2, '=');
+			let name = parts.next().unwrap_or("").trim();
+			let weight: u32 = parts.next()
+				.ok_or_else(|| format!("missing weight in tx-mix entry {:?}", entry))?
+				.trim()
+				.parse()
+				.map_err(|_| format!("invalid weight in tx-mix entry {:?}", entry))?;
+
+			let kind = match name {
+				"transfer" => CallKind::Transfer,
+				"faucet" => CallKind::Faucet,
+				"remark" => CallKind::Remark,
+				other => return Err(format!("unknown tx-mix call kind {:?}", other)),
+			};
+
+			Ok(CallKindWeight { kind, weight })
+		})
+		.collect()
+}
+
+/// Picks a `CallKind` from `mix` in proportion to its weight, using
+/// `selector` (e.g. a running transaction counter) as the draw in
+/// `0..total_weight`. Falls back to `CallKind::Transfer` if `mix` is empty.
+pub fn pick_call_kind(mix: &[CallKindWeight], selector: u32) -> CallKind {
+	let total: u32 = mix.iter().map(|w| w.weight).sum();
+	if total == 0 {
+		return CallKind::Transfer;
+	}
+
+	let mut remaining = selector % total;
+	for entry in mix {
+		if remaining < entry.weight {
+			return entry.kind;
+		}
+		remaining -= entry.weight;
+	}
+
+	CallKind::Transfer
+}
+
 pub struct RuntimeAdapterImpl;
 
+impl RuntimeAdapterImpl {
+	/// Gives the adapter a handle to the node's client/backend so it can
+	/// answer nonce, era and timestamp queries from chain state at a given
+	/// block instead of returning placeholder constants. Must be called once
+	/// before manufacturing any extrinsics.
+	pub fn init(client: Arc<FullClient>) {
+		*CLIENT.write() = Some(client);
+	}
+
+	/// Selects which `CryptoScheme` subsequently generated accounts and
+	/// signatures use. Defaults to `Sr25519`, today's behaviour, until
+	/// changed.
+	pub fn set_scheme(scheme: CryptoScheme) {
+		*SCHEME.write() = scheme;
+	}
+
+	fn scheme() -> CryptoScheme {
+		*SCHEME.read()
+	}
+
+	fn client() -> Arc<FullClient> {
+		CLIENT.read().clone()
+			.expect("RuntimeAdapterImpl::init was not called before use")
+	}
+
+	/// The master account's nonce for its next `Faucet` extrinsic: seeded
+	/// from live storage the first time it's needed, then incremented
+	/// in-process so a whole manufacturing round (many calls against the
+	/// same `prior_block_hash`, before any of them have landed) hands out a
+	/// distinct nonce per call instead of querying the same pre-call value
+	/// every time.
+	fn next_master_index(prior_block_hash: Hash) -> <Self as RuntimeAdapter>::Index {
+		let mut master_index = MASTER_INDEX.write();
+		let index = master_index.unwrap_or_else(|| {
+			Self::extract_index(Self::master_account_id(), prior_block_hash)
+		});
+		*master_index = Some(index + 1);
+		index
+	}
+
+	fn storage<T: Decode>(at: Hash, key: &[u8]) -> Option<T> {
+		Self::client()
+			.storage(&BlockId::Hash(at), &StorageKey(key.to_vec()))
+			.ok()
+			.and_then(|data| data)
+			.and_then(|data| Decode::decode(&mut &data.0[..]))
+	}
+
+	/// Manufactures an extrinsic of the given `CallKind`, the pluggable
+	/// counterpart to `transfer_extrinsic` that lets a tx-mix run emit a
+	/// blend of call shapes instead of pure transfers.
+	pub fn call_extrinsic(
+		kind: CallKind,
+		sender: &<Self as RuntimeAdapter>::AccountId,
+		key: &<Self as RuntimeAdapter>::Secret,
+		destination: &<Self as RuntimeAdapter>::AccountId,
+		amount: <Self as RuntimeAdapter>::Balance,
+		index: <Self as RuntimeAdapter>::Index,
+		phase: <Self as RuntimeAdapter>::Phase,
+		prior_block_hash: &<<Self as RuntimeAdapter>::Block as BlockT>::Hash,
+	) -> <<Self as RuntimeAdapter>::Block as BlockT>::Extrinsic {
+		match kind {
+			CallKind::Transfer => Self::transfer_extrinsic(
+				sender, key, destination, amount, index, phase, prior_block_hash,
+			),
+			CallKind::Faucet => {
+				// The master account, not `sender`, is the signer here, so it
+				// needs its own nonce -- reusing `index` (which is `sender`'s)
+				// is only correct by coincidence on the very first faucet call.
+				let master_id = Self::master_account_id();
+				let master_index = Self::next_master_index(*prior_block_hash);
+				sign::<service::Factory, Self>(CheckedExtrinsic {
+					signed: Some((master_id, master_index)),
+					function: Call::Balances(
+						BalancesCall::transfer(
+							indices::address::Address::Id(sender.clone().into()),
+							amount.into(),
+						)
+					),
+				}, &Self::master_account_secret(), &prior_block_hash, phase.as_())
+			}
+			CallKind::Remark => sign::<service::Factory, Self>(CheckedExtrinsic {
+				signed: Some((sender.clone(), index)),
+				function: Call::System(system::Call::remark(Vec::new())),
+			}, key, &prior_block_hash, phase.as_()),
+		}
+	}
+}
+
 impl RuntimeAdapter for RuntimeAdapterImpl {
 	type AccountId = node_primitives::AccountId;
 	type Balance = node_primitives::Balance;
 	type Moment = node_primitives::Timestamp;
 	type Index = node_primitives::Index;
 	type Phase = sr_primitives::generic::Phase;
-	type Secret = sr25519::Pair;
+	type Secret = AnySecret;
 	type Block = node_primitives::Block;
 
 	fn transfer_extrinsic(
@@ -84,53 +334,76 @@ impl RuntimeAdapter for RuntimeAdapterImpl {
 	}
 
 	fn minimum_balance() -> Self::Balance {
-		// TODO get correct amount via api. See #2587.
-		1337
+		<Runtime as balances::Trait>::ExistentialDeposit::get()
 	}
 
 	fn minimum_period() -> Self::Moment {
-		// TODO get via api: <timestamp::Module<T>>::minimum_period(). See #2587.
-		99
+		<Runtime as timestamp::Trait>::MinimumPeriod::get()
 	}
 
+	/// The well-known "Alice" account, under whichever `CryptoScheme` is
+	/// currently selected. Derived from `master_account_secret` rather than
+	/// hardcoded separately, so the id always matches the scheme the master
+	/// account is actually signing with.
 	fn master_account_id() -> Self::AccountId {
-		Keyring::Alice.pair().public()
+		Self::master_account_secret().account_id()
 	}
 
+	/// The well-known "Alice" keypair under whichever `CryptoScheme` is
+	/// currently selected, the same well-known seed `Keyring::Alice` uses for
+	/// `sr25519` but re-derived per scheme via `Pair::from_string` so a run
+	/// under `Ed25519`/`Ecdsa` gets a matching master/funding account instead
+	/// of a scheme-mismatched `sr25519` one.
 	fn master_account_secret() -> Self::Secret {
-		Keyring::Alice.pair()
+		match Self::scheme() {
+			CryptoScheme::Sr25519 => AnySecret::Sr25519(Keyring::Alice.pair()),
+			CryptoScheme::Ed25519 => AnySecret::Ed25519(
+				ed25519::Pair::from_string("//Alice", None)
+					.expect("\"//Alice\" is a valid derivation path; qed")
+			),
+			CryptoScheme::Ecdsa => AnySecret::Ecdsa(
+				ecdsa::Pair::from_string("//Alice", None)
+					.expect("\"//Alice\" is a valid derivation path; qed")
+			),
+		}
 	}
 
-	/// Generates a random `AccountId` from `seed`.
+	/// Generates a random `AccountId` from `seed`, under the scheme selected
+	/// via `RuntimeAdapterImpl::set_scheme`.
 	fn gen_random_account_id(seed: u64) -> Self::AccountId {
-		let pair: sr25519::Pair = sr25519::Pair::from_seed(gen_seed_bytes(seed));
-		pair.public().into()
+		Self::gen_random_account_secret(seed).account_id()
 	}
 
-	/// Generates a random `Secret` from `seed`.
+	/// Generates a random `Secret` from `seed`, under the scheme selected via
+	/// `RuntimeAdapterImpl::set_scheme`.
 	fn gen_random_account_secret(seed: u64) -> Self::Secret {
-		let pair: sr25519::Pair = sr25519::Pair::from_seed(gen_seed_bytes(seed));
-		pair
+		AnySecret::from_seed(Self::scheme(), gen_seed_bytes(seed))
 	}
 
-	fn extract_timestamp(_block_hash: <Self::Block as BlockT>::Hash) -> Self::Moment {
-		// TODO get correct timestamp from inherent. See #2587.
-		let now = SystemTime::now();
-		now.duration_since(UNIX_EPOCH)
-			.expect("now always later than unix epoch; qed").as_secs()
+	fn extract_timestamp(block_hash: <Self::Block as BlockT>::Hash) -> Self::Moment {
+		Self::storage(block_hash, &timestamp::Now::<Runtime>::key())
+			.unwrap_or_else(|| {
+				let now = SystemTime::now();
+				now.duration_since(UNIX_EPOCH)
+					.expect("now always later than unix epoch; qed").as_secs()
+			})
 	}
 
 	fn extract_index(
-		_account_id: Self::AccountId,
-		_block_hash: <Self::Block as BlockT>::Hash,
+		account_id: Self::AccountId,
+		block_hash: <Self::Block as BlockT>::Hash,
 	) -> Self::Index {
-		// TODO get correct index for account via api. See #2587.
-		0.as_()
+		Self::storage(block_hash, &system::AccountNonce::<Runtime>::key_for(&account_id))
+			.unwrap_or(0)
 	}
 
-	fn extract_phase(_block_hash: <Self::Block as BlockT>::Hash) -> Self::Phase {
-		// TODO get correct phase via api. See #2587.
-		0.as_()
+	fn extract_phase(block_hash: <Self::Block as BlockT>::Hash) -> Self::Phase {
+		let number = Self::client().header(&BlockId::Hash(block_hash))
+			.ok()
+			.and_then(|header| header)
+			.map(|header| header.number().as_())
+			.unwrap_or(0);
+		(number % MORTAL_PERIOD).as_()
 	}
 }
 
@@ -144,27 +417,137 @@ fn gen_seed_bytes(seed: u64) -> [u8; 32] {
 	seed_bytes
 }
 
+impl RuntimeAdapterImpl {
+	/// Derives a `Secret` from a BIP39 `phrase` the same way a user's keystore
+	/// would, rather than from an opaque numeric seed: validate the mnemonic to
+	/// recover its entropy, stretch it into a 64-byte seed via PBKDF2-HMAC-SHA512
+	/// (2048 iterations, salt `"mnemonic" || passphrase`), take the first 32
+	/// bytes as the schnorrkel mini-secret key, and finally apply each
+	/// `//hard`/`/soft` junction in `derivation_path` in turn, in the style of
+	/// Substrate's secret-URI syntax.
+	pub fn secret_from_mnemonic(
+		phrase: &str,
+		passphrase: Option<&str>,
+		derivation_path: &str,
+	) -> Result<<Self as RuntimeAdapter>::Secret, String> {
+		let mnemonic = Mnemonic::from_phrase(phrase, Language::English)
+			.map_err(|e| format!("invalid mnemonic phrase: {}", e))?;
+
+		let seed = mini_secret_from_entropy(mnemonic.entropy(), passphrase.unwrap_or(""))
+			.map_err(|e| format!("unable to derive seed from entropy: {}", e))?;
+
+		let mut mini_secret = [0u8; 32];
+		mini_secret.copy_from_slice(&seed.as_bytes()[..32]);
+
+		let root = sr25519::Pair::from_seed(mini_secret);
+
+		derive_junctions(derivation_path)?.into_iter()
+			.try_fold(root, |pair, junction| pair.derive(Some(junction).into_iter()))
+			.map(AnySecret::Sr25519)
+			.map_err(|_| "invalid derivation path".into())
+	}
+}
+
+/// Splits a Substrate-style derivation suffix, e.g. `//hard/soft`, into the
+/// sequence of junctions `Pair::derive` expects. A `//` prefix produces a hard
+/// junction, a single `/` a soft one. Returns an error if `path` is non-empty
+/// and doesn't start with a `/` separator, rather than silently treating the
+/// first character as one.
+///
+/// Mirrors `DeriveJunction`'s own secret-URI parsing: a segment that parses as
+/// a `u64` (e.g. `//0`, `//1`) is encoded as that number, not as the literal
+/// digit string, so a numeric path derives the same `Secret`/`AccountId` a
+/// real keystore would -- encoding the digits themselves would silently
+/// diverge for every numeric index.
+fn derive_junctions(path: &str) -> Result<Vec<DeriveJunction>, String> {
+	let mut junctions = Vec::new();
+	let mut rest = path;
+
+	while !rest.is_empty() {
+		if !rest.starts_with('/') {
+			return Err(format!(
+				"malformed derivation path {:?}: expected a '/' or \"//\" separator", path,
+			));
+		}
+
+		let hard = rest.starts_with("//");
+		rest = &rest[if hard { 2 } else { 1 }..];
+
+		let end = rest.find('/').unwrap_or_else(|| rest.len());
+		let (part, remainder) = rest.split_at(end);
+		rest = remainder;
+
+		junctions.push(match part.parse::<u64>() {
+			Ok(index) => if hard { DeriveJunction::hard(index) } else { DeriveJunction::soft(index) },
+			Err(_) => if hard { DeriveJunction::hard(part) } else { DeriveJunction::soft(part) },
+		});
+	}
+
+	Ok(junctions)
+}
+
+/// Builds the `Era` mixed into a signed extrinsic's payload, so a runtime can
+/// plug in its own mortality policy (e.g. a shorter longevity) without
+/// forking `sign`. The `phase` handed to `build` is the prior block's number
+/// modulo `MORTAL_PERIOD`, so it is a correct era phase only for periods of
+/// at most `MORTAL_PERIOD` blocks.
+///
+/// This only covers the era, not a genuinely richer `SignedExtra`: the wire
+/// `UncheckedExtrinsic` built in `sign` is `node_runtime`'s concrete type,
+/// which carries exactly `(Address, Signature, Index, Era)`. Extension data
+/// beyond that -- `ChargeTransactionPayment` tips, spec/tx version, genesis
+/// hash, metadata hash -- isn't representable on the wire until
+/// `node_runtime`'s `UncheckedExtrinsic`/`SignedExtra` grows those fields too;
+/// adding them here would only hash them into the signed payload without the
+/// node ever being able to reconstruct and verify it.
+pub trait ExtrinsicParams<RA: RuntimeAdapter> {
+	/// Builds the era for an extrinsic carrying nonce `index`, with `phase` as its
+	/// era phase and `prior_block_hash` as its mortal checkpoint.
+	fn build(index: RA::Index, phase: u64, prior_block_hash: &Hash) -> Era;
+}
+
+/// Reproduces today's behaviour: a mortal era of `MORTAL_PERIOD` blocks.
+pub struct DefaultExtrinsicParams;
+
+impl<RA: RuntimeAdapter> ExtrinsicParams<RA> for DefaultExtrinsicParams {
+	fn build(_index: RA::Index, phase: u64, _prior_block_hash: &Hash) -> Era {
+		Era::mortal(MORTAL_PERIOD, phase)
+	}
+}
+
+/// A `RuntimeAdapter` that knows which `ExtrinsicParams` to sign with, so
+/// `sign` can be shared across runtimes whose mortality policy doesn't match
+/// `DefaultExtrinsicParams` rather than recompiling a fixed `Era` per runtime.
+pub trait SignExtrinsic: RuntimeAdapter {
+	type Params: ExtrinsicParams<Self>;
+}
+
+impl SignExtrinsic for RuntimeAdapterImpl {
+	type Params = DefaultExtrinsicParams;
+}
+
 /// Creates an `UncheckedExtrinsic` containing the appropriate signature for
-/// a `CheckedExtrinsics`.
-fn sign<F: ServiceFactory, RA: RuntimeAdapter>(
+/// a `CheckedExtrinsic`, using `RA::Params` to build the era mixed into the
+/// signed payload.
+fn sign<F: ServiceFactory, RA: SignExtrinsic>(
 	xt: CheckedExtrinsic,
-	key: &sr25519::Pair,
+	key: &AnySecret,
 	prior_block_hash: &Hash,
 	phase: u64,
 ) -> <RA::Block as BlockT>::Extrinsic {
 	let s = match xt.signed {
 		Some((signed, index)) => {
-			let era = Era::mortal(256, phase);
+			let era = RA::Params::build(index, phase, prior_block_hash);
 			let payload = (index.into(), xt.function, era, prior_block_hash);
-			let signature = payload.using_encoded(|b| {
+			let signature: MultiSignature = payload.using_encoded(|b| {
 				if b.len() > 256 {
 					key.sign(&sr_io::blake2_256(b))
 				} else {
 					key.sign(b)
 				}
-			}).into();
+			});
 			UncheckedExtrinsic {
-				signature: Some((indices::address::Address::Id(signed), signature, payload.0, era)),
+				signature: Some((indices::address::Address::Id(signed), signature.into(), payload.0, era)),
 				function: payload.1,
 			}
 		}
@@ -177,3 +560,166 @@ fn sign<F: ServiceFactory, RA: RuntimeAdapter>(
 	let e = Encode::encode(&s);
 	Decode::decode(&mut &e[..]).expect("Failed to decode signed unchecked extrinsic")
 }
+
+/// Final landed/dropped counts for a factory run's confirmation subsystem.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct ConfirmationTally {
+	pub landed: u64,
+	pub dropped: u64,
+}
+
+/// An optional feedback loop for a factory run: rather than signing and
+/// handing extrinsics off blind, it watches *finalized* blocks (never just
+/// best-chain ones, so a reorg of a non-finalized fork can't manufacture a
+/// false confirmation) for the account nonce passing the index an extrinsic
+/// was submitted with, and reports how long that took.
+///
+/// A run that doesn't need the feedback loop can skip `Confirmation`
+/// entirely and stay a fire-and-forget emitter.
+pub struct Confirmation {
+	client: Arc<FullClient>,
+	submitted: RwLock<HashMap<(node_primitives::AccountId, node_primitives::Index), Instant>>,
+}
+
+impl Confirmation {
+	/// Creates a confirmation tracker bound to `client`. Call `submitted`
+	/// right after handing an extrinsic to the pool, and spawn `watch` once
+	/// to drive it off the finalized-block feed.
+	pub fn new(client: Arc<FullClient>) -> Self {
+		Confirmation { client, submitted: RwLock::new(HashMap::new()) }
+	}
+
+	/// Records that `account` just submitted an extrinsic carrying `index` as
+	/// its nonce, starting its latency clock.
+	pub fn submitted(&self, account: node_primitives::AccountId, index: node_primitives::Index) {
+		self.submitted.write().insert((account, index), Instant::now());
+	}
+
+	/// Drives the confirmation loop. For every finalized block, each
+	/// still-pending `(account, index)` is checked against that block's
+	/// `system::AccountNonce`; once the nonce has advanced past `index`, the
+	/// submitted extrinsic has landed, so it's logged with its finalization
+	/// latency and dropped from the pending set. A production subscriber
+	/// wanting the exact extrinsic (rather than just "something from this
+	/// account landed") would instead match against `system::Events` for the
+	/// relevant event, e.g. `balances::Transfer`.
+	pub fn watch(self: Arc<Self>) -> impl Future<Item = (), Error = ()> {
+		let this = self.clone();
+		self.client.finality_notification_stream()
+			.for_each(move |notification| {
+				let at = notification.hash;
+				let mut pending = this.submitted.write();
+				let landed: Vec<_> = pending.iter()
+					.filter_map(|(key, _)| {
+						let (account, index) = key;
+						let nonce: node_primitives::Index = RuntimeAdapterImpl::storage(
+							at,
+							&system::AccountNonce::<Runtime>::key_for(account),
+						).unwrap_or(0);
+						if nonce > *index { Some(key.clone()) } else { None }
+					})
+					.collect();
+
+				for key in landed {
+					if let Some(submitted_at) = pending.remove(&key) {
+						let latency: Duration = submitted_at.elapsed();
+						info!("tx-factory: {:?} finalized after {:?}", key, latency);
+					}
+				}
+
+				Ok(())
+			})
+			.map_err(|_| ())
+	}
+
+	/// A landed/dropped tally given `total_submitted` extrinsics were handed
+	/// off over the run -- whatever is still pending never finalized and
+	/// counts as dropped.
+	pub fn tally(&self, total_submitted: u64) -> ConfirmationTally {
+		tally_landed_dropped(total_submitted, self.submitted.read().len() as u64)
+	}
+}
+
+/// The landed/dropped tally math itself, split out of `Confirmation::tally`
+/// so it's testable without a live client.
+fn tally_landed_dropped(total_submitted: u64, still_pending: u64) -> ConfirmationTally {
+	ConfirmationTally {
+		landed: total_submitted.saturating_sub(still_pending),
+		dropped: still_pending,
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn derive_junctions_splits_hard_and_soft() {
+		let junctions = derive_junctions("//hard/soft").unwrap();
+		assert_eq!(junctions.len(), 2);
+	}
+
+	#[test]
+	fn derive_junctions_empty_path_has_no_junctions() {
+		assert!(derive_junctions("").unwrap().is_empty());
+	}
+
+	#[test]
+	fn derive_junctions_rejects_path_missing_leading_separator() {
+		assert!(derive_junctions("hard").is_err());
+	}
+
+	#[test]
+	fn derive_junctions_encodes_numeric_segments_as_numbers_not_strings() {
+		let numeric = derive_junctions("//0").unwrap();
+		let numeric_hard = DeriveJunction::hard(0u64);
+		assert_eq!(numeric, vec![numeric_hard]);
+
+		let non_numeric = derive_junctions("//0x").unwrap();
+		let string_hard = DeriveJunction::hard("0x");
+		assert_eq!(non_numeric, vec![string_hard]);
+	}
+
+	#[test]
+	fn parse_tx_mix_parses_kinds_and_weights() {
+		let mix = parse_tx_mix("transfer=70,remark=30").unwrap();
+		assert_eq!(mix.len(), 2);
+		assert_eq!(mix[0].kind, CallKind::Transfer);
+		assert_eq!(mix[0].weight, 70);
+		assert_eq!(mix[1].kind, CallKind::Remark);
+		assert_eq!(mix[1].weight, 30);
+	}
+
+	#[test]
+	fn parse_tx_mix_rejects_unknown_kind() {
+		assert!(parse_tx_mix("not-a-kind=100").is_err());
+	}
+
+	#[test]
+	fn pick_call_kind_distributes_in_proportion_to_weight() {
+		let mix = parse_tx_mix("transfer=70,remark=30").unwrap();
+		assert_eq!(pick_call_kind(&mix, 0), CallKind::Transfer);
+		assert_eq!(pick_call_kind(&mix, 69), CallKind::Transfer);
+		assert_eq!(pick_call_kind(&mix, 70), CallKind::Remark);
+		assert_eq!(pick_call_kind(&mix, 99), CallKind::Remark);
+	}
+
+	#[test]
+	fn pick_call_kind_falls_back_to_transfer_for_empty_mix() {
+		assert_eq!(pick_call_kind(&[], 5), CallKind::Transfer);
+	}
+
+	#[test]
+	fn tally_landed_dropped_counts_still_pending_as_dropped() {
+		let tally = tally_landed_dropped(10, 3);
+		assert_eq!(tally.landed, 7);
+		assert_eq!(tally.dropped, 3);
+	}
+
+	#[test]
+	fn tally_landed_dropped_saturates_when_nothing_submitted() {
+		let tally = tally_landed_dropped(0, 3);
+		assert_eq!(tally.landed, 0);
+		assert_eq!(tally.dropped, 3);
+	}
+}